@@ -0,0 +1,147 @@
+//! VCS-agnostic backend trait.
+//!
+//! `gnb` dispatches to a [`Backend`] implementation based on which VCS
+//! marker directory (`.git`, `.jj`, `.hg`) is found, walking up from the
+//! current directory the same way each tool's own CLI does. Third
+//! parties can implement `Backend` for other VCSes without touching the
+//! rest of `gnb`.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use anyhow::Result;
+
+use crate::git::GitBackend;
+use crate::hg::HgBackend;
+use crate::jj::JjBackend;
+
+/// Operations `gnb` needs from the underlying VCS.
+pub trait Backend {
+    /// Whether the current directory is (inside) a repository for this VCS.
+    fn is_repo(&self) -> bool;
+    /// All local branch/bookmark names.
+    fn list_local_branches(&self) -> Result<HashSet<String>>;
+    /// Remote branch/bookmark names starting with `prefix`.
+    fn list_remote_branches(&self, prefix: &str) -> Result<HashSet<String>>;
+    /// Whether a remote with the given name is configured.
+    fn has_remote(&self, name: &str) -> bool;
+    /// The conventional name of this VCS's default remote (`origin` for
+    /// git and jj, `default` for hg's `hg paths`).
+    fn default_remote(&self) -> &'static str;
+    /// The repository's working-tree root, if it can be determined.
+    fn root(&self) -> Option<PathBuf>;
+    /// Create a new branch/bookmark named `name`, cut from `from` (or the
+    /// current position if `None`), optionally switching to it.
+    fn create_branch(&self, name: &str, from: Option<&str>, switch: bool) -> Result<()>;
+    /// Push `name` to this VCS's default remote (see `default_remote`);
+    /// the git backend also sets it as the branch's upstream.
+    fn push(&self, name: &str) -> Result<()>;
+}
+
+/// Detect which VCS is in use and return the matching backend.
+///
+/// `use_cli` only affects the git backend, forcing it to shell out to the
+/// `git` binary instead of opening the repository in-process.
+pub fn detect(use_cli: bool) -> Box<dyn Backend> {
+    match find_marker() {
+        Some(Marker::Jj) => Box::new(JjBackend),
+        Some(Marker::Hg) => Box::new(HgBackend),
+        _ => Box::new(GitBackend::open(use_cli)),
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Marker {
+    Jj,
+    Hg,
+}
+
+/// Walk up from the current directory looking for a `.jj` or `.hg`
+/// marker. `.git` is left to the git backend, which does its own (more
+/// thorough) repository discovery — unless it's sharing a directory with
+/// a `.jj` or `.hg` marker, which is what `jj git init --colocate` (and
+/// hg's equivalent) produce; a jj/hg marker there wins, since the point
+/// of colocation is to keep using jj/hg as the primary interface.
+fn find_marker() -> Option<Marker> {
+    find_marker_from(&std::env::current_dir().ok()?)
+}
+
+fn find_marker_from(start: &std::path::Path) -> Option<Marker> {
+    let mut dir: PathBuf = start.to_path_buf();
+
+    loop {
+        if dir.join(".jj").exists() {
+            return Some(Marker::Jj);
+        }
+        if dir.join(".hg").exists() {
+            return Some(Marker::Hg);
+        }
+        if dir.join(".git").exists() {
+            return None;
+        }
+        if !dir.pop() {
+            return None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("gnb_test_backend_{}", name));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("nested")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_find_marker_from_detects_jj() {
+        let dir = scratch_dir("jj");
+        std::fs::create_dir_all(dir.join(".jj")).unwrap();
+
+        assert_eq!(find_marker_from(&dir.join("nested")), Some(Marker::Jj));
+    }
+
+    #[test]
+    fn test_find_marker_from_detects_hg() {
+        let dir = scratch_dir("hg");
+        std::fs::create_dir_all(dir.join(".hg")).unwrap();
+
+        assert_eq!(find_marker_from(&dir.join("nested")), Some(Marker::Hg));
+    }
+
+    #[test]
+    fn test_find_marker_from_prefers_colocated_jj_over_git() {
+        let dir = scratch_dir("git_and_jj_colocated");
+        std::fs::create_dir_all(dir.join(".git")).unwrap();
+        std::fs::create_dir_all(dir.join(".jj")).unwrap();
+
+        assert_eq!(find_marker_from(&dir.join("nested")), Some(Marker::Jj));
+    }
+
+    #[test]
+    fn test_find_marker_from_prefers_colocated_hg_over_git() {
+        let dir = scratch_dir("git_and_hg_colocated");
+        std::fs::create_dir_all(dir.join(".git")).unwrap();
+        std::fs::create_dir_all(dir.join(".hg")).unwrap();
+
+        assert_eq!(find_marker_from(&dir.join("nested")), Some(Marker::Hg));
+    }
+
+    #[test]
+    fn test_find_marker_from_stops_at_plain_git_without_colocation() {
+        let dir = scratch_dir("plain_git");
+        std::fs::create_dir_all(dir.join(".git")).unwrap();
+
+        assert_eq!(find_marker_from(&dir.join("nested")), None);
+    }
+
+    #[test]
+    fn test_find_marker_from_none_found() {
+        let dir = scratch_dir("none");
+
+        assert_eq!(find_marker_from(&dir.join("nested")), None);
+    }
+}
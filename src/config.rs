@@ -0,0 +1,328 @@
+//! Layered configuration for branch-name templates.
+//!
+//! Settings are resolved in three layers, each overriding the previous
+//! key-by-key: built-in defaults, `~/.config/gnb/config.toml` (or
+//! `$XDG_CONFIG_HOME/gnb/config.toml`), then a repo-local `.gnb.toml` at
+//! the root of the current repository.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::format::{Item, StrftimeItems};
+use serde::Deserialize;
+
+use crate::backend::Backend;
+
+/// How numeric collision suffixes are rendered (`_2` vs. `-2`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SuffixStyle {
+    Underscore,
+    Dash,
+}
+
+impl SuffixStyle {
+    /// Render `candidate` with the `n`th collision suffix applied.
+    pub fn format(self, candidate: &str, n: usize) -> String {
+        match self {
+            SuffixStyle::Underscore => format!("{}_{}", candidate, n),
+            SuffixStyle::Dash => format!("{}-{}", candidate, n),
+        }
+    }
+
+    fn parse(raw: &str) -> Result<Self> {
+        match raw {
+            "numeric" | "underscore" => Ok(SuffixStyle::Underscore),
+            "dash" => Ok(SuffixStyle::Dash),
+            other => anyhow::bail!(
+                "Unknown suffix_style '{}': expected 'numeric' or 'dash'",
+                other
+            ),
+        }
+    }
+}
+
+/// Resolved configuration used to build a branch name.
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub template: String,
+    pub separator: String,
+    pub date_format: String,
+    pub suffix_style: SuffixStyle,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            template: "{prefix}/{name}".to_string(),
+            separator: "-".to_string(),
+            date_format: "%y%m%d".to_string(),
+            suffix_style: SuffixStyle::Underscore,
+        }
+    }
+}
+
+/// Shape of a single `config.toml` / `.gnb.toml` file. Every field is
+/// optional so a layer only needs to specify the keys it overrides.
+#[derive(Debug, Default, Deserialize)]
+struct ConfigFile {
+    template: Option<String>,
+    separator: Option<String>,
+    date_format: Option<String>,
+    suffix_style: Option<String>,
+}
+
+impl ConfigFile {
+    fn merge_into(self, config: &mut Config) -> Result<()> {
+        if let Some(template) = self.template {
+            config.template = template;
+        }
+        if let Some(separator) = self.separator {
+            validate_separator(&separator)?;
+            config.separator = separator;
+        }
+        if let Some(date_format) = self.date_format {
+            validate_date_format(&date_format)?;
+            config.date_format = date_format;
+        }
+        if let Some(suffix_style) = self.suffix_style {
+            config.suffix_style = SuffixStyle::parse(&suffix_style)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Reject a `separator` that would smuggle an invalid ref character
+/// straight into sanitized output. `sanitize_component` (in `main.rs`)
+/// inserts the configured separator verbatim wherever it strips a
+/// disallowed character, so the separator itself has to obey the same
+/// `git check-ref-format` rules as everything else: no ASCII control
+/// characters, no whitespace, and none of `~ ^ : ? * [ \ / @`.
+fn validate_separator(separator: &str) -> Result<()> {
+    if separator.is_empty() {
+        anyhow::bail!("Invalid separator: must not be empty");
+    }
+
+    if separator.chars().any(|c| {
+        c.is_control()
+            || c.is_whitespace()
+            || matches!(c, '~' | '^' | ':' | '?' | '*' | '[' | '\\' | '/' | '@')
+    }) {
+        anyhow::bail!(
+            "Invalid separator '{}': must not contain whitespace or any of ~ ^ : ? * [ \\ / @",
+            separator
+        );
+    }
+
+    Ok(())
+}
+
+/// Reject a `date_format` that `chrono` can't render. `DelayedFormat`'s
+/// `Display` impl panics on a bad strftime specifier instead of returning
+/// an error, so this has to be checked up front rather than left to
+/// surface wherever the format string finally gets rendered.
+fn validate_date_format(date_format: &str) -> Result<()> {
+    if StrftimeItems::new(date_format).any(|item| item == Item::Error) {
+        anyhow::bail!("Invalid date_format '{}': unrecognized strftime specifier", date_format);
+    }
+
+    Ok(())
+}
+
+/// Load and merge the user-global and repo-local config layers.
+pub fn load(backend: &dyn Backend) -> Result<Config> {
+    let mut config = Config::default();
+
+    if let Some(path) = user_config_path() {
+        if let Some(file) = read_config_file(&path)? {
+            file.merge_into(&mut config)?;
+        }
+    }
+
+    if let Some(path) = repo_config_path(backend) {
+        if let Some(file) = read_config_file(&path)? {
+            file.merge_into(&mut config)?;
+        }
+    }
+
+    Ok(config)
+}
+
+fn user_config_path() -> Option<PathBuf> {
+    if let Ok(xdg) = std::env::var("XDG_CONFIG_HOME") {
+        if !xdg.trim().is_empty() {
+            return Some(PathBuf::from(xdg).join("gnb").join("config.toml"));
+        }
+    }
+
+    let home = std::env::var("HOME").ok()?;
+    Some(PathBuf::from(home).join(".config").join("gnb").join("config.toml"))
+}
+
+fn repo_config_path(backend: &dyn Backend) -> Option<PathBuf> {
+    backend.root().map(|root| root.join(".gnb.toml"))
+}
+
+fn read_config_file(path: &Path) -> Result<Option<ConfigFile>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+    let parsed: ConfigFile = toml::from_str(&contents)
+        .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
+
+    Ok(Some(parsed))
+}
+
+/// Substitute `{placeholder}` values in `template` from `context`.
+///
+/// An unknown placeholder is an error rather than expanding to an empty
+/// string, so a typo in a user's template surfaces immediately.
+pub fn render_template(template: &str, context: &HashMap<String, String>) -> Result<String> {
+    let mut result = String::with_capacity(template.len());
+    let mut chars = template.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '{' {
+            result.push(c);
+            continue;
+        }
+
+        let mut key = String::new();
+        let mut closed = false;
+        for nc in chars.by_ref() {
+            if nc == '}' {
+                closed = true;
+                break;
+            }
+            key.push(nc);
+        }
+
+        if !closed {
+            anyhow::bail!("Unterminated placeholder in template: '{{{}'", key);
+        }
+
+        let value = context
+            .get(key.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Unknown placeholder '{{{}}}' in template", key))?;
+        result.push_str(value);
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_render_template_default() {
+        let ctx = context(&[("prefix", "alice"), ("name", "fix-login")]);
+        assert_eq!(
+            render_template("{prefix}/{name}", &ctx).unwrap(),
+            "alice/fix-login"
+        );
+    }
+
+    #[test]
+    fn test_render_template_all_placeholders() {
+        let ctx = context(&[
+            ("prefix", "alice"),
+            ("name", "login"),
+            ("date", "240101"),
+            ("ticket", "ABC-123"),
+        ]);
+        assert_eq!(
+            render_template("{prefix}/{ticket}-{date}-{name}", &ctx).unwrap(),
+            "alice/ABC-123-240101-login"
+        );
+    }
+
+    #[test]
+    fn test_render_template_unknown_placeholder_errors() {
+        let ctx = context(&[("prefix", "alice")]);
+        assert!(render_template("{prefix}/{bogus}", &ctx).is_err());
+    }
+
+    #[test]
+    fn test_suffix_style_parse() {
+        assert_eq!(SuffixStyle::parse("numeric").unwrap(), SuffixStyle::Underscore);
+        assert_eq!(SuffixStyle::parse("dash").unwrap(), SuffixStyle::Dash);
+        assert!(SuffixStyle::parse("bogus").is_err());
+    }
+
+    #[test]
+    fn test_suffix_style_format() {
+        assert_eq!(SuffixStyle::Underscore.format("user/a", 2), "user/a_2");
+        assert_eq!(SuffixStyle::Dash.format("user/a", 2), "user/a-2");
+    }
+
+    #[test]
+    fn test_validate_date_format_rejects_unknown_specifier() {
+        assert!(validate_date_format("%y%m%d").is_ok());
+        assert!(validate_date_format("%Q").is_err());
+    }
+
+    #[test]
+    fn test_validate_separator_rejects_ref_format_chars() {
+        assert!(validate_separator("-").is_ok());
+        assert!(validate_separator("_").is_ok());
+        assert!(validate_separator(":").is_err());
+        assert!(validate_separator("~").is_err());
+        assert!(validate_separator(" ").is_err());
+        assert!(validate_separator("@").is_err());
+        assert!(validate_separator("").is_err());
+    }
+
+    #[test]
+    fn test_merge_into_rejects_invalid_separator() {
+        let mut config = Config::default();
+        let file = ConfigFile {
+            template: None,
+            separator: Some(":".to_string()),
+            date_format: None,
+            suffix_style: None,
+        };
+        assert!(file.merge_into(&mut config).is_err());
+        assert_eq!(config.separator, "-");
+    }
+
+    #[test]
+    fn test_merge_into_rejects_invalid_date_format() {
+        let mut config = Config::default();
+        let file = ConfigFile {
+            template: None,
+            separator: None,
+            date_format: Some("%Q".to_string()),
+            suffix_style: None,
+        };
+        assert!(file.merge_into(&mut config).is_err());
+        assert_eq!(config.date_format, "%y%m%d");
+    }
+
+    #[test]
+    fn test_config_file_merge_overrides_only_set_keys() {
+        let mut config = Config::default();
+        let file = ConfigFile {
+            template: Some("{prefix}_{name}".to_string()),
+            separator: None,
+            date_format: None,
+            suffix_style: Some("dash".to_string()),
+        };
+        file.merge_into(&mut config).unwrap();
+
+        assert_eq!(config.template, "{prefix}_{name}");
+        assert_eq!(config.separator, "-");
+        assert_eq!(config.suffix_style, SuffixStyle::Dash);
+    }
+}
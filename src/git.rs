@@ -0,0 +1,425 @@
+//! Git backend, in-process via `git2` by default with a `--use-cli`
+//! fallback to the `git` binary on PATH.
+//!
+//! The in-process path avoids spawning a subprocess for every call and
+//! parsing its stdout; it's kept alongside the CLI path for environments
+//! where libgit2 can't open the repository (e.g. unusual filesystems or
+//! partial clones).
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use git2::{BranchType, Config as GitConfig, Cred, Direction, RemoteCallbacks, Repository};
+
+use crate::backend::Backend;
+
+/// Build the credential callbacks used when connecting to a remote
+/// in-process, mirroring the auth sources plain `git` falls back to:
+/// an ssh-agent for SSH remotes, then the configured credential helper
+/// for everything else.
+fn remote_callbacks() -> RemoteCallbacks<'static> {
+    let mut callbacks = RemoteCallbacks::new();
+    callbacks.credentials(|url, username_from_url, allowed_types| {
+        if allowed_types.contains(git2::CredentialType::SSH_KEY) {
+            if let Some(username) = username_from_url {
+                if let Ok(cred) = Cred::ssh_key_from_agent(username) {
+                    return Ok(cred);
+                }
+            }
+        }
+
+        if let Ok(config) = GitConfig::open_default() {
+            if let Ok(cred) = Cred::credential_helper(&config, url, username_from_url) {
+                return Ok(cred);
+            }
+        }
+
+        Cred::default()
+    });
+    callbacks
+}
+
+/// Git backend, either holding an open `git2` repository or shelling out
+/// to the `git` binary.
+pub enum GitBackend {
+    Lib(Repository),
+    Cli,
+}
+
+impl GitBackend {
+    /// Open the git backend, preferring the in-process library unless
+    /// `use_cli` is set or the library can't open the current directory
+    /// as a repository.
+    pub fn open(use_cli: bool) -> Self {
+        if use_cli {
+            return GitBackend::Cli;
+        }
+
+        match Repository::discover(".") {
+            Ok(repo) => GitBackend::Lib(repo),
+            Err(_) => GitBackend::Cli,
+        }
+    }
+
+    fn list_remote_branches_lib(repo: &Repository, prefix: &str) -> Result<HashSet<String>> {
+        let mut remote = repo
+            .find_remote("origin")
+            .context("Failed to look up origin remote")?;
+        let connection = remote
+            .connect_auth(Direction::Fetch, Some(remote_callbacks()), None)
+            .context("Failed to connect to origin")?;
+
+        let ref_prefix = format!("refs/heads/{}", prefix);
+        let mut names = HashSet::new();
+        for head in connection.list().context("Failed to list remote refs")? {
+            if head.name().starts_with(&ref_prefix) {
+                if let Some(short) = head.name().strip_prefix("refs/heads/") {
+                    names.insert(short.to_string());
+                }
+            }
+        }
+
+        Ok(names)
+    }
+
+    fn list_remote_branches_cli(prefix: &str) -> Result<HashSet<String>> {
+        let pattern = format!("refs/heads/{}*", prefix);
+        let output = Command::new("git")
+            .args(["ls-remote", "--heads", "origin", &pattern])
+            .output()
+            .context("Failed to list remote branches")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("Failed to list remote branches: {}", stderr.trim());
+        }
+
+        let stdout = String::from_utf8(output.stdout)
+            .context("Remote branch list was not valid UTF-8")?;
+        let mut branches = HashSet::new();
+        for line in stdout.lines() {
+            let mut parts = line.split_whitespace();
+            let _ = parts.next();
+            let Some(ref_name) = parts.next() else {
+                continue;
+            };
+            if let Some(short) = ref_name.strip_prefix("refs/heads/") {
+                branches.insert(short.to_string());
+            }
+        }
+
+        Ok(branches)
+    }
+}
+
+impl Backend for GitBackend {
+    fn is_repo(&self) -> bool {
+        match self {
+            GitBackend::Lib(_) => true,
+            GitBackend::Cli => Command::new("git")
+                .args(["rev-parse", "--is-inside-work-tree"])
+                .status()
+                .map(|status| status.success())
+                .unwrap_or(false),
+        }
+    }
+
+    fn list_local_branches(&self) -> Result<HashSet<String>> {
+        match self {
+            GitBackend::Lib(repo) => {
+                let branches = repo
+                    .branches(Some(BranchType::Local))
+                    .context("Failed to list local branches")?;
+
+                let mut names = HashSet::new();
+                for entry in branches {
+                    let (branch, _) = entry.context("Failed to read local branch")?;
+                    if let Some(name) = branch
+                        .name()
+                        .context("Local branch name was not valid UTF-8")?
+                    {
+                        names.insert(name.to_string());
+                    }
+                }
+
+                Ok(names)
+            }
+            GitBackend::Cli => {
+                let output = Command::new("git")
+                    .args(["for-each-ref", "--format=%(refname:short)", "refs/heads"])
+                    .output()
+                    .context("Failed to list local branches")?;
+
+                if !output.status.success() {
+                    let stderr = String::from_utf8_lossy(&output.stderr);
+                    anyhow::bail!("Failed to list local branches: {}", stderr.trim());
+                }
+
+                let stdout = String::from_utf8(output.stdout)
+                    .context("Local branch list was not valid UTF-8")?;
+                let mut branches = HashSet::new();
+                for line in stdout.lines() {
+                    let name = line.trim();
+                    if !name.is_empty() {
+                        branches.insert(name.to_string());
+                    }
+                }
+
+                Ok(branches)
+            }
+        }
+    }
+
+    fn list_remote_branches(&self, prefix: &str) -> Result<HashSet<String>> {
+        if !self.has_remote("origin") {
+            return Ok(HashSet::new());
+        }
+
+        match self {
+            // The in-process connection needs the same credential sources
+            // (ssh-agent, credential helper) that plain `git` relies on to
+            // authenticate; if that still doesn't work (e.g. an interactive
+            // prompt is required), fall back to the CLI rather than bailing.
+            GitBackend::Lib(repo) => match Self::list_remote_branches_lib(repo, prefix) {
+                Ok(names) => Ok(names),
+                Err(_) => Self::list_remote_branches_cli(prefix),
+            },
+            GitBackend::Cli => Self::list_remote_branches_cli(prefix),
+        }
+    }
+
+    fn has_remote(&self, name: &str) -> bool {
+        match self {
+            GitBackend::Lib(repo) => repo.find_remote(name).is_ok(),
+            GitBackend::Cli => Command::new("git")
+                .args(["remote", "get-url", name])
+                .status()
+                .map(|status| status.success())
+                .unwrap_or(false),
+        }
+    }
+
+    fn default_remote(&self) -> &'static str {
+        "origin"
+    }
+
+    fn root(&self) -> Option<PathBuf> {
+        match self {
+            GitBackend::Lib(repo) => repo.workdir().map(|p| p.to_path_buf()),
+            GitBackend::Cli => {
+                let output = Command::new("git")
+                    .args(["rev-parse", "--show-toplevel"])
+                    .output()
+                    .ok()?;
+
+                if !output.status.success() {
+                    return None;
+                }
+
+                let root = String::from_utf8(output.stdout).ok()?;
+                Some(PathBuf::from(root.trim()))
+            }
+        }
+    }
+
+    fn create_branch(&self, name: &str, from: Option<&str>, switch: bool) -> Result<()> {
+        match self {
+            GitBackend::Lib(repo) => {
+                let commit = match from {
+                    Some(reference) => repo
+                        .revparse_single(reference)
+                        .with_context(|| format!("Failed to resolve ref: {}", reference))?
+                        .peel_to_commit()
+                        .with_context(|| format!("Ref does not point to a commit: {}", reference))?,
+                    None => repo
+                        .head()
+                        .context("Failed to resolve HEAD")?
+                        .peel_to_commit()
+                        .context("HEAD does not point to a commit")?,
+                };
+
+                let branch = repo
+                    .branch(name, &commit, false)
+                    .with_context(|| format!("Failed to create branch: {}", name))?;
+
+                if !switch {
+                    return Ok(());
+                }
+
+                let refname = branch
+                    .get()
+                    .name()
+                    .context("New branch ref name was not valid UTF-8")?
+                    .to_string();
+
+                repo.set_head(&refname)
+                    .context("Failed to set HEAD to new branch")?;
+                repo.checkout_head(None)
+                    .context("Failed to check out new branch")?;
+
+                Ok(())
+            }
+            GitBackend::Cli => {
+                if !switch {
+                    let mut args = vec!["branch", name];
+                    if let Some(reference) = from {
+                        args.push(reference);
+                    }
+
+                    let output = Command::new("git")
+                        .args(&args)
+                        .output()
+                        .context("Failed to run git branch")?;
+
+                    if !output.status.success() {
+                        let stderr = String::from_utf8_lossy(&output.stderr);
+                        anyhow::bail!("Failed to create branch: {}", stderr.trim());
+                    }
+
+                    return Ok(());
+                }
+
+                // Try git switch first (modern git)
+                let mut switch_args = vec!["switch", "-c", name];
+                if let Some(reference) = from {
+                    switch_args.push(reference);
+                }
+
+                let switch_result = Command::new("git")
+                    .args(&switch_args)
+                    .output()
+                    .context("Failed to run git switch")?;
+
+                if switch_result.status.success() {
+                    return Ok(());
+                }
+
+                // Fall back to git checkout -b (older git)
+                let mut checkout_args = vec!["checkout", "-b", name];
+                if let Some(reference) = from {
+                    checkout_args.push(reference);
+                }
+
+                let checkout = Command::new("git")
+                    .args(&checkout_args)
+                    .output()
+                    .context("Failed to run git checkout")?;
+
+                if !checkout.status.success() {
+                    let stderr = String::from_utf8_lossy(&checkout.stderr);
+                    anyhow::bail!("Failed to create branch: {}", stderr.trim());
+                }
+
+                Ok(())
+            }
+        }
+    }
+
+    fn push(&self, name: &str) -> Result<()> {
+        // Pushing needs credentials (SSH agent, credential helper, etc.);
+        // shelling out to git lets it reuse whatever the user already has
+        // configured instead of wiring up git2's credential callbacks.
+        let output = Command::new("git")
+            .args(["push", "-u", "origin", name])
+            .output()
+            .context("Failed to run git push")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("Failed to push branch: {}", stderr.trim());
+        }
+
+        Ok(())
+    }
+}
+
+/// Collect local and default-remote branches matching the candidate prefix.
+pub fn collect_existing_branches(backend: &dyn Backend, candidate: &str) -> Result<HashSet<String>> {
+    let mut existing = backend.list_local_branches()?;
+
+    if backend.has_remote(backend.default_remote()) {
+        let remote = backend.list_remote_branches(candidate)?;
+        existing.extend(remote);
+    }
+
+    Ok(existing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FakeBackend {
+        local: HashSet<String>,
+        remote: HashSet<String>,
+        has_remote: bool,
+    }
+
+    impl Backend for FakeBackend {
+        fn is_repo(&self) -> bool {
+            true
+        }
+
+        fn list_local_branches(&self) -> Result<HashSet<String>> {
+            Ok(self.local.clone())
+        }
+
+        fn list_remote_branches(&self, prefix: &str) -> Result<HashSet<String>> {
+            Ok(self
+                .remote
+                .iter()
+                .filter(|name| name.starts_with(prefix))
+                .cloned()
+                .collect())
+        }
+
+        fn has_remote(&self, _name: &str) -> bool {
+            self.has_remote
+        }
+
+        fn default_remote(&self) -> &'static str {
+            "origin"
+        }
+
+        fn root(&self) -> Option<PathBuf> {
+            None
+        }
+
+        fn create_branch(&self, _name: &str, _from: Option<&str>, _switch: bool) -> Result<()> {
+            Ok(())
+        }
+
+        fn push(&self, _name: &str) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_collect_existing_branches_merges_local_and_remote() {
+        let backend = FakeBackend {
+            local: HashSet::from(["alice/a".to_string()]),
+            remote: HashSet::from(["alice/b".to_string(), "bob/c".to_string()]),
+            has_remote: true,
+        };
+
+        let existing = collect_existing_branches(&backend, "alice/").unwrap();
+        assert_eq!(
+            existing,
+            HashSet::from(["alice/a".to_string(), "alice/b".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_collect_existing_branches_skips_remote_when_none_configured() {
+        let backend = FakeBackend {
+            local: HashSet::from(["alice/a".to_string()]),
+            remote: HashSet::from(["alice/b".to_string()]),
+            has_remote: false,
+        };
+
+        let existing = collect_existing_branches(&backend, "alice/").unwrap();
+        assert_eq!(existing, HashSet::from(["alice/a".to_string()]));
+    }
+}
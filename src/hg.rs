@@ -0,0 +1,208 @@
+//! Mercurial (`hg`) backend.
+//!
+//! Mercurial bookmarks are the closest analog to a git branch: a bookmark
+//! follows commits made while it's active, which is what `gnb` wants
+//! after creating one.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+
+use crate::backend::Backend;
+
+pub struct HgBackend;
+
+impl Backend for HgBackend {
+    fn is_repo(&self) -> bool {
+        Command::new("hg")
+            .args(["root"])
+            .output()
+            .map(|output| output.status.success())
+            .unwrap_or(false)
+    }
+
+    fn list_local_branches(&self) -> Result<HashSet<String>> {
+        let output = Command::new("hg")
+            .args(["bookmarks", "--template", "{bookmark}\n"])
+            .output()
+            .context("Failed to list hg bookmarks")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("Failed to list hg bookmarks: {}", stderr.trim());
+        }
+
+        let stdout =
+            String::from_utf8(output.stdout).context("hg bookmarks output was not valid UTF-8")?;
+        Ok(stdout
+            .lines()
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::to_string)
+            .collect())
+    }
+
+    fn list_remote_branches(&self, prefix: &str) -> Result<HashSet<String>> {
+        // Mercurial has no local cache of remote bookmarks comparable to
+        // git's remote-tracking refs, so this only guards against local
+        // collisions plus whatever the active remote exposes via `hg
+        // incoming --bookmarks`, best-effort and network-dependent.
+        let output = Command::new("hg")
+            .args(["incoming", "--bookmarks", "--template", "{bookmark}\n"])
+            .output();
+
+        let Ok(output) = output else {
+            return Ok(HashSet::new());
+        };
+
+        if !output.status.success() {
+            return Ok(HashSet::new());
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        Ok(stdout
+            .lines()
+            .map(str::trim)
+            .filter(|name| name.starts_with(prefix))
+            .map(str::to_string)
+            .collect())
+    }
+
+    fn has_remote(&self, name: &str) -> bool {
+        Command::new("hg")
+            .args(["paths"])
+            .output()
+            .map(|output| {
+                output.status.success()
+                    && String::from_utf8_lossy(&output.stdout)
+                        .lines()
+                        .any(|line| line.split('=').next().map(str::trim) == Some(name))
+            })
+            .unwrap_or(false)
+    }
+
+    fn default_remote(&self) -> &'static str {
+        // Mercurial's conventional path name for the clone source is
+        // `default`, not git's `origin`.
+        "default"
+    }
+
+    fn root(&self) -> Option<PathBuf> {
+        let output = Command::new("hg").args(["root"]).output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+
+        let root = String::from_utf8(output.stdout).ok()?;
+        Some(PathBuf::from(root.trim()))
+    }
+
+    fn create_branch(&self, name: &str, from: Option<&str>, switch: bool) -> Result<()> {
+        let (update_args, bookmark_args) = create_branch_args(name, from, switch);
+
+        // A bookmark only becomes "active" (follows the working copy on
+        // commit) if it's created while the working copy is already
+        // parented at its revision, so moving there is only correct when
+        // we actually want to switch. With --no-switch, `-r` points the
+        // bookmark at the target revision without touching the checkout.
+        if let Some(args) = update_args {
+            let reference = args[1];
+            let output = Command::new("hg")
+                .args(&args)
+                .output()
+                .with_context(|| format!("Failed to update to ref: {}", reference))?;
+
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                anyhow::bail!("Failed to update to ref {}: {}", reference, stderr.trim());
+            }
+        }
+
+        let output = Command::new("hg")
+            .args(&bookmark_args)
+            .output()
+            .context("Failed to run hg bookmark")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("Failed to create bookmark: {}", stderr.trim());
+        }
+
+        Ok(())
+    }
+
+    fn push(&self, name: &str) -> Result<()> {
+        let output = Command::new("hg")
+            .args(["push", "--bookmark", name])
+            .output()
+            .context("Failed to run hg push")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("Failed to push bookmark: {}", stderr.trim());
+        }
+
+        Ok(())
+    }
+}
+
+/// Build the `hg` argvs needed to create (and optionally switch to) a
+/// bookmark: an optional `hg update <from>` to run first when switching
+/// to an explicit `--from`, and the `hg bookmark` call itself. Pulled out
+/// as a pure function so the switch/--from/--no-switch argument
+/// construction is covered by tests without shelling out to `hg`.
+fn create_branch_args<'a>(
+    name: &'a str,
+    from: Option<&'a str>,
+    switch: bool,
+) -> (Option<Vec<&'a str>>, Vec<&'a str>) {
+    if switch {
+        let update_args = from.map(|reference| vec!["update", reference]);
+        (update_args, vec!["bookmark", name])
+    } else {
+        let mut bookmark_args = vec!["bookmark", name, "--inactive"];
+        if let Some(reference) = from {
+            bookmark_args.push("-r");
+            bookmark_args.push(reference);
+        }
+        (None, bookmark_args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_branch_args_switch_no_from() {
+        let (update, bookmark) = create_branch_args("feature", None, true);
+        assert_eq!(update, None);
+        assert_eq!(bookmark, vec!["bookmark", "feature"]);
+    }
+
+    #[test]
+    fn test_create_branch_args_switch_with_from() {
+        let (update, bookmark) = create_branch_args("feature", Some("main"), true);
+        assert_eq!(update, Some(vec!["update", "main"]));
+        assert_eq!(bookmark, vec!["bookmark", "feature"]);
+    }
+
+    #[test]
+    fn test_create_branch_args_no_switch_no_from() {
+        let (update, bookmark) = create_branch_args("feature", None, false);
+        assert_eq!(update, None);
+        assert_eq!(bookmark, vec!["bookmark", "feature", "--inactive"]);
+    }
+
+    #[test]
+    fn test_create_branch_args_no_switch_with_from() {
+        let (update, bookmark) = create_branch_args("feature", Some("main"), false);
+        assert_eq!(update, None);
+        assert_eq!(
+            bookmark,
+            vec!["bookmark", "feature", "--inactive", "-r", "main"]
+        );
+    }
+}
@@ -0,0 +1,210 @@
+//! Jujutsu (`jj`) backend.
+//!
+//! `jj` has no concept of a checked-out branch the way git does — the
+//! working copy is always a commit, and a "branch" is a bookmark that can
+//! point anywhere. Creating one at the current working-copy revision
+//! (`@`) is the closest equivalent to `gnb`'s git behavior. With
+//! `--from`, the bookmark is created elsewhere, so switching to it means
+//! explicitly moving the working copy there afterward.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+
+use crate::backend::Backend;
+
+pub struct JjBackend;
+
+impl Backend for JjBackend {
+    fn is_repo(&self) -> bool {
+        Command::new("jj")
+            .args(["root"])
+            .output()
+            .map(|output| output.status.success())
+            .unwrap_or(false)
+    }
+
+    fn list_local_branches(&self) -> Result<HashSet<String>> {
+        let output = Command::new("jj")
+            .args(["bookmark", "list", "--template", "name ++ \"\\n\""])
+            .output()
+            .context("Failed to list jj bookmarks")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("Failed to list jj bookmarks: {}", stderr.trim());
+        }
+
+        let stdout =
+            String::from_utf8(output.stdout).context("jj bookmark list was not valid UTF-8")?;
+        Ok(stdout
+            .lines()
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::to_string)
+            .collect())
+    }
+
+    fn list_remote_branches(&self, prefix: &str) -> Result<HashSet<String>> {
+        let output = Command::new("jj")
+            .args([
+                "bookmark",
+                "list",
+                "--all-remotes",
+                "--template",
+                "name ++ \"\\n\"",
+            ])
+            .output()
+            .context("Failed to list jj remote bookmarks")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("Failed to list jj remote bookmarks: {}", stderr.trim());
+        }
+
+        let stdout = String::from_utf8(output.stdout)
+            .context("jj bookmark list was not valid UTF-8")?;
+        Ok(stdout
+            .lines()
+            .map(str::trim)
+            .filter(|name| name.starts_with(prefix))
+            .map(str::to_string)
+            .collect())
+    }
+
+    fn has_remote(&self, name: &str) -> bool {
+        Command::new("jj")
+            .args(["git", "remote", "list"])
+            .output()
+            .map(|output| {
+                output.status.success()
+                    && String::from_utf8_lossy(&output.stdout)
+                        .lines()
+                        .any(|line| line.split_whitespace().next() == Some(name))
+            })
+            .unwrap_or(false)
+    }
+
+    fn default_remote(&self) -> &'static str {
+        "origin"
+    }
+
+    fn root(&self) -> Option<PathBuf> {
+        let output = Command::new("jj").args(["root"]).output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+
+        let root = String::from_utf8(output.stdout).ok()?;
+        Some(PathBuf::from(root.trim()))
+    }
+
+    fn create_branch(&self, name: &str, from: Option<&str>, switch: bool) -> Result<()> {
+        // With no --from, the bookmark is created at "@" (the working
+        // copy), so the `jj edit` that `switch` adds leaves the working
+        // copy where it is. With --from, the bookmark points elsewhere,
+        // and `switch` decides whether the working copy follows it there.
+        let (bookmark_args, edit_args) = create_branch_args(name, from, switch);
+
+        let output = Command::new("jj")
+            .args(&bookmark_args)
+            .output()
+            .context("Failed to run jj bookmark create")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("Failed to create bookmark: {}", stderr.trim());
+        }
+
+        let Some(edit_args) = edit_args else {
+            return Ok(());
+        };
+
+        let edit = Command::new("jj")
+            .args(&edit_args)
+            .output()
+            .with_context(|| format!("Failed to move working copy to bookmark: {}", name))?;
+
+        if !edit.status.success() {
+            let stderr = String::from_utf8_lossy(&edit.stderr);
+            anyhow::bail!(
+                "Failed to move working copy to bookmark {}: {}",
+                name,
+                stderr.trim()
+            );
+        }
+
+        Ok(())
+    }
+
+    fn push(&self, name: &str) -> Result<()> {
+        let output = Command::new("jj")
+            .args(["git", "push", "--bookmark", name])
+            .output()
+            .context("Failed to run jj git push")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("Failed to push bookmark: {}", stderr.trim());
+        }
+
+        Ok(())
+    }
+}
+
+/// Build the `jj` argvs needed to create (and optionally switch to) a
+/// bookmark: the `jj bookmark create` call, and an optional `jj edit` to
+/// move the working copy there when `switch` is set. Pulled out as a
+/// pure function so the switch/--from/--no-switch argument construction
+/// is covered by tests without shelling out to `jj`.
+fn create_branch_args<'a>(
+    name: &'a str,
+    from: Option<&'a str>,
+    switch: bool,
+) -> (Vec<&'a str>, Option<Vec<&'a str>>) {
+    let revision = from.unwrap_or("@");
+    let bookmark_args = vec!["bookmark", "create", name, "-r", revision];
+    let edit_args = switch.then(|| vec!["edit", name]);
+    (bookmark_args, edit_args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_branch_args_switch_no_from() {
+        let (bookmark, edit) = create_branch_args("feature", None, true);
+        assert_eq!(bookmark, vec!["bookmark", "create", "feature", "-r", "@"]);
+        assert_eq!(edit, Some(vec!["edit", "feature"]));
+    }
+
+    #[test]
+    fn test_create_branch_args_switch_with_from() {
+        let (bookmark, edit) = create_branch_args("feature", Some("main"), true);
+        assert_eq!(
+            bookmark,
+            vec!["bookmark", "create", "feature", "-r", "main"]
+        );
+        assert_eq!(edit, Some(vec!["edit", "feature"]));
+    }
+
+    #[test]
+    fn test_create_branch_args_no_switch_no_from() {
+        let (bookmark, edit) = create_branch_args("feature", None, false);
+        assert_eq!(bookmark, vec!["bookmark", "create", "feature", "-r", "@"]);
+        assert_eq!(edit, None);
+    }
+
+    #[test]
+    fn test_create_branch_args_no_switch_with_from() {
+        let (bookmark, edit) = create_branch_args("feature", Some("main"), false);
+        assert_eq!(
+            bookmark,
+            vec!["bookmark", "create", "feature", "-r", "main"]
+        );
+        assert_eq!(edit, None);
+    }
+}
@@ -7,9 +7,17 @@ use anyhow::{Context, Result};
 use chrono::Local;
 use clap::Parser;
 use colored::Colorize;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::process::{Command, ExitCode};
 
+mod backend;
+mod config;
+mod git;
+mod hg;
+mod jj;
+
+use config::SuffixStyle;
+
 /// Create a new git branch prefixed with your username.
 ///
 /// Examples:
@@ -24,19 +32,52 @@ use std::process::{Command, ExitCode};
 #[command(about = "Create git branches with username prefix")]
 #[command(after_help = "\
 Examples:
-  gnb            Create username/YYMMDD branch
-  gnb ABC-123    Create username/ABC-123 branch
-  gnb fix login  Create username/fix-login branch
+  gnb                       Create username/YYMMDD branch
+  gnb ABC-123               Create username/ABC-123 branch
+  gnb fix login             Create username/fix-login branch
+  gnb --from main ABC-123   Cut the branch from main instead of HEAD
+  gnb --push ABC-123        Create the branch and push it to origin
+  gnb --check weird-name    Print the would-be branch name, don't create it
 
 Environment:
   GNB_PREFIX    Override username prefix (e.g., GNB_PREFIX=ci-bot)
 
-The branch is created from current HEAD. Existing branch names get
-a numeric suffix (_2, _3, etc.) to avoid collisions.")]
+Config:
+  ~/.config/gnb/config.toml and a repo-local .gnb.toml let you customize
+  the branch `template`, `separator`, `date_format`, and `suffix_style`.
+  Repo-local values override user-global ones.
+
+The branch is created from current HEAD by default. Existing branch
+names get a numeric suffix (_2, _3, etc.) to avoid collisions. The
+branch name is a trailing var-arg, so flags must come before it.")]
 struct Cli {
-    /// Branch name (defaults to YYMMDD date if not provided)
+    /// Branch name (defaults to YYMMDD date if not provided). Must come
+    /// after any flags, since it greedily consumes the rest of argv.
     #[arg(trailing_var_arg = true)]
     name: Vec<String>,
+
+    /// Shell out to the git binary instead of using the in-process library (git repos only)
+    #[arg(long)]
+    use_cli: bool,
+
+    /// Cut the new branch from this ref instead of the current HEAD
+    #[arg(long, value_name = "REF")]
+    from: Option<String>,
+
+    /// Push the new branch to the default remote (origin; hg's is default),
+    /// setting up tracking on git
+    #[arg(long)]
+    push: bool,
+
+    /// Create the branch without switching to it
+    #[arg(long)]
+    no_switch: bool,
+
+    /// Print the branch name that would be created and exit, without
+    /// touching the repository. Exits non-zero if the name has no valid
+    /// ref components left after sanitizing.
+    #[arg(long)]
+    check: bool,
 }
 
 fn main() -> ExitCode {
@@ -52,53 +93,84 @@ fn main() -> ExitCode {
 fn run() -> Result<()> {
     let cli = Cli::parse();
 
-    // Ensure we're in a git repo
-    ensure_git_repo()?;
+    // Detect the VCS (.git, .jj, or .hg) and open the matching backend
+    let repo = backend::detect(cli.use_cli);
+    if !repo.is_repo() {
+        anyhow::bail!("Not inside a git, jj, or hg repository");
+    }
+
+    if cli.push && !cli.check && !repo.has_remote(repo.default_remote()) {
+        anyhow::bail!("--push requires a '{}' remote", repo.default_remote());
+    }
+
+    // Load layered config (defaults <- user-global <- repo-local)
+    let config = config::load(repo.as_ref())?;
 
     // Get the prefix (username or GNB_PREFIX override)
-    let prefix = get_prefix()?;
+    let prefix = get_prefix(&config.separator)?;
 
     // Build the base branch name
-    let base = build_base_name(&cli.name);
+    let base = build_base_name(&cli.name, &config.date_format);
+    let ticket = extract_ticket(&base).unwrap_or_default();
+    let date = today_stamp(&config.date_format);
 
     // Sanitize the base name
-    let sanitized = sanitize(&base);
-
-    // Build candidate branch name
-    let candidate = format!("{}/{}", prefix, sanitized);
+    let name = sanitize(&base, &config.separator);
 
-    // Find an available branch name (handles collisions)
-    let existing = collect_existing_branches(&candidate)?;
-    let target = pick_available_name(&candidate, &existing)?;
+    // Render the template, then sanitize the full result component-by-component
+    let mut context = HashMap::new();
+    context.insert("prefix".to_string(), prefix.clone());
+    context.insert("name".to_string(), name);
+    context.insert("date".to_string(), date.clone());
+    context.insert("ticket".to_string(), ticket);
 
-    // Create and switch to the branch
-    create_branch(&target)?;
+    let rendered = config::render_template(&config.template, &context)?;
+    let candidate = sanitize_candidate(&rendered, &config.separator, &date);
 
-    println!(
-        "{} Created and switched to branch: {}",
-        "✅".green(),
-        target.cyan()
-    );
+    // Find an available branch name (handles collisions)
+    let existing = git::collect_existing_branches(repo.as_ref(), &candidate)?;
+    let target = pick_available_name(&candidate, &existing, config.suffix_style)?;
+
+    if cli.check {
+        println!("{}", target);
+        if !has_valid_ref_components(&rendered, &config.separator) {
+            anyhow::bail!(
+                "'{}' has no valid ref components left after sanitizing",
+                rendered
+            );
+        }
+        return Ok(());
+    }
 
-    Ok(())
-}
+    // Create the branch, optionally cut from --from and switching to it
+    let switch = !cli.no_switch;
+    repo.create_branch(&target, cli.from.as_deref(), switch)?;
 
-/// Ensure we're inside a git repository.
-fn ensure_git_repo() -> Result<()> {
-    let output = Command::new("git")
-        .args(["rev-parse", "--is-inside-work-tree"])
-        .output()
-        .context("Failed to run git")?;
+    if switch {
+        println!(
+            "{} Created and switched to branch: {}",
+            "✅".green(),
+            target.cyan()
+        );
+    } else {
+        println!("{} Created branch: {}", "✅".green(), target.cyan());
+    }
 
-    if !output.status.success() {
-        anyhow::bail!("Not inside a git repository");
+    if cli.push {
+        repo.push(&target)?;
+        println!(
+            "{} Pushed {} to {}",
+            "✅".green(),
+            target.cyan(),
+            repo.default_remote()
+        );
     }
 
     Ok(())
 }
 
 /// Get the branch prefix (username or GNB_PREFIX override).
-fn get_prefix() -> Result<String> {
+fn get_prefix(separator: &str) -> Result<String> {
     let mut from_env = false;
 
     // Check for GNB_PREFIX environment variable first
@@ -125,7 +197,7 @@ fn get_prefix() -> Result<String> {
         }
     };
 
-    let sanitized = sanitize_component(raw_prefix.trim());
+    let sanitized = sanitize_component(raw_prefix.trim(), separator);
     if sanitized.is_empty() {
         if from_env {
             anyhow::bail!("GNB_PREFIX is empty or invalid after sanitization");
@@ -137,50 +209,67 @@ fn get_prefix() -> Result<String> {
 }
 
 /// Build the base branch name from CLI arguments.
-fn build_base_name(args: &[String]) -> String {
+fn build_base_name(args: &[String], date_format: &str) -> String {
     if args.is_empty() {
-        // Default to YYMMDD format
-        today_stamp()
+        // Default to the configured date format
+        today_stamp(date_format)
     } else {
         // Join all arguments with spaces
         args.join(" ")
     }
 }
 
-fn today_stamp() -> String {
-    Local::now().format("%y%m%d").to_string()
+fn today_stamp(date_format: &str) -> String {
+    Local::now().format(date_format).to_string()
+}
+
+/// Extract a ticket key like `ABC-123` from a base name, if it looks like one.
+fn extract_ticket(name: &str) -> Option<String> {
+    let (letters, digits) = name.split_once('-')?;
+    if letters.is_empty() || digits.is_empty() {
+        return None;
+    }
+
+    let is_ticket = letters.chars().all(|c| c.is_ascii_alphabetic())
+        && digits.chars().all(|c| c.is_ascii_digit());
+
+    is_ticket.then(|| name.to_string())
 }
 
 /// Sanitize a single branch name component to be git-compatible.
-fn sanitize_component(name: &str) -> String {
+///
+/// `is_allowed_branch_char` only lets through alphanumerics plus `. _ + -`,
+/// so every character `git check-ref-format` forbids — ASCII control
+/// characters and DEL, space, and `~ ^ : ? * [ \` — is already routed
+/// through [`push_separator`] like any other disallowed character. That
+/// same path is what keeps a lone `@` or an `@{` sequence from surviving:
+/// `@` and `{` are both disallowed, so either one collapses to a single
+/// separator and gets trimmed off if it ends up at a component edge.
+fn sanitize_component(name: &str, separator: &str) -> String {
     let mut result = String::with_capacity(name.len());
     let mut prev_was_separator = false;
     let mut prev_was_dot = false;
 
-    for mut c in name.chars() {
-        if c == '/' {
-            c = '-';
-        }
-
-        if c.is_whitespace() {
-            push_separator(&mut result, &mut prev_was_separator, &mut prev_was_dot);
+    for c in name.chars() {
+        if c == '/' || c.is_whitespace() {
+            push_separator(&mut result, &mut prev_was_separator, &mut prev_was_dot, separator);
             continue;
         }
 
         if !is_allowed_branch_char(c) {
-            push_separator(&mut result, &mut prev_was_separator, &mut prev_was_dot);
+            push_separator(&mut result, &mut prev_was_separator, &mut prev_was_dot, separator);
             continue;
         }
 
         if c == '.' {
             if result.is_empty() || prev_was_separator {
-                push_separator(&mut result, &mut prev_was_separator, &mut prev_was_dot);
+                push_separator(&mut result, &mut prev_was_separator, &mut prev_was_dot, separator);
                 continue;
             }
 
             if prev_was_dot {
                 result.pop();
-                push_separator(&mut result, &mut prev_was_separator, &mut prev_was_dot);
+                push_separator(&mut result, &mut prev_was_separator, &mut prev_was_dot, separator);
                 continue;
             }
 
@@ -191,15 +280,26 @@ fn sanitize_component(name: &str) -> String {
         }
 
         result.push(c);
-        prev_was_separator = c == '-';
+        prev_was_separator = result.ends_with(separator);
         prev_was_dot = false;
     }
 
-    let mut cleaned = result.trim_matches(|c| c == '-' || c == '.').to_string();
+    // `trim_matches` only strips one char at a time, which can't recognize
+    // a multi-char separator (e.g. "--"); strip it, '.', and a leading '-'
+    // (forbidden by git check-ref-format on its own, independent of the
+    // configured separator) a whole match at a time instead.
+    let mut cleaned = result.as_str();
+    while let Some(rest) = strip_leading_boundary(cleaned, separator) {
+        cleaned = rest;
+    }
+    while let Some(rest) = strip_trailing_boundary(cleaned, separator) {
+        cleaned = rest;
+    }
+    let mut cleaned = cleaned.to_string();
 
     if cleaned.ends_with(".lock") {
         let dot_index = cleaned.len() - ".lock".len();
-        cleaned.replace_range(dot_index..dot_index + 1, "-");
+        cleaned.replace_range(dot_index..dot_index + 1, separator);
     }
 
     cleaned
@@ -209,106 +309,89 @@ fn is_allowed_branch_char(c: char) -> bool {
     c.is_alphanumeric() || matches!(c, '.' | '_' | '+' | '-')
 }
 
-fn push_separator(result: &mut String, prev_was_separator: &mut bool, prev_was_dot: &mut bool) {
+/// Strip one leading `separator`, `.`, or `-` from `s`, if present.
+/// A leading `-` is stripped unconditionally (not just when it matches
+/// `separator`) because `git check-ref-format` forbids a ref component
+/// from starting with one, regardless of what the separator is.
+fn strip_leading_boundary<'a>(s: &'a str, separator: &str) -> Option<&'a str> {
+    s.strip_prefix(separator)
+        .or_else(|| s.strip_prefix('.'))
+        .or_else(|| s.strip_prefix('-'))
+}
+
+/// Strip one trailing `separator` or `.` from `s`, if present.
+fn strip_trailing_boundary<'a>(s: &'a str, separator: &str) -> Option<&'a str> {
+    s.strip_suffix(separator).or_else(|| s.strip_suffix('.'))
+}
+
+fn push_separator(
+    result: &mut String,
+    prev_was_separator: &mut bool,
+    prev_was_dot: &mut bool,
+    separator: &str,
+) {
     if !*prev_was_separator {
-        result.push('-');
+        result.push_str(separator);
         *prev_was_separator = true;
     }
     *prev_was_dot = false;
 }
 
 /// Sanitize a branch name to be git-compatible.
-fn sanitize(name: &str) -> String {
-    let sanitized = sanitize_component(name);
+fn sanitize(name: &str, separator: &str) -> String {
+    let sanitized = sanitize_component(name, separator);
     if sanitized.is_empty() {
-        today_stamp()
+        today_stamp("%y%m%d")
     } else {
         sanitized
     }
 }
 
-/// Collect local and origin branches matching the candidate prefix.
-fn collect_existing_branches(candidate: &str) -> Result<HashSet<String>> {
-    let mut existing = collect_local_branches()?;
-
-    if has_origin_remote() {
-        let remote = collect_remote_branches(candidate)?;
-        existing.extend(remote);
-    }
-
-    Ok(existing)
-}
-
-fn collect_local_branches() -> Result<HashSet<String>> {
-    let output = Command::new("git")
-        .args(["for-each-ref", "--format=%(refname:short)", "refs/heads"])
-        .output()
-        .context("Failed to list local branches")?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("Failed to list local branches: {}", stderr.trim());
-    }
-
-    let stdout =
-        String::from_utf8(output.stdout).context("Local branch list was not valid UTF-8")?;
-    let mut branches = HashSet::new();
-    for line in stdout.lines() {
-        let name = line.trim();
-        if !name.is_empty() {
-            branches.insert(name.to_string());
-        }
-    }
-
-    Ok(branches)
-}
-
-fn collect_remote_branches(candidate: &str) -> Result<HashSet<String>> {
-    let pattern = format!("refs/heads/{}*", candidate);
-    let output = Command::new("git")
-        .args(["ls-remote", "--heads", "origin", &pattern])
-        .output()
-        .context("Failed to list remote branches")?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("Failed to list remote branches: {}", stderr.trim());
-    }
-
-    let stdout =
-        String::from_utf8(output.stdout).context("Remote branch list was not valid UTF-8")?;
-    let mut branches = HashSet::new();
-    for line in stdout.lines() {
-        let mut parts = line.split_whitespace();
-        let _ = parts.next();
-        let Some(ref_name) = parts.next() else {
-            continue;
-        };
-        if let Some(short) = ref_name.strip_prefix("refs/heads/") {
-            branches.insert(short.to_string());
-        }
+/// Sanitize a fully-rendered template result, treating `/` as a path
+/// separator and validating each component independently so a custom
+/// template can't smuggle an invalid ref component through untouched.
+fn sanitize_candidate(rendered: &str, separator: &str, fallback: &str) -> String {
+    let parts: Vec<String> = rendered
+        .split('/')
+        .map(|part| sanitize_component(part, separator))
+        .filter(|part| !part.is_empty())
+        .collect();
+
+    if parts.is_empty() {
+        fallback.to_string()
+    } else {
+        parts.join("/")
     }
-
-    Ok(branches)
 }
 
-fn has_origin_remote() -> bool {
-    Command::new("git")
-        .args(["remote", "get-url", "origin"])
-        .status()
-        .map(|status| status.success())
-        .unwrap_or(false)
+/// Whether at least one slash-separated component of `rendered` survives
+/// sanitizing with something left that's actually a valid ref component.
+/// `sanitize_candidate` silently drops empty components and falls back to
+/// a date stamp if all of them vanish, which is the right behavior when
+/// actually creating a branch but would hide the problem from `--check`.
+/// `sanitize_component` already strips a leading `-`, but it's checked
+/// again here explicitly since a component starting with `-` is the one
+/// `git check-ref-format` rule `--check` most needs to catch.
+fn has_valid_ref_components(rendered: &str, separator: &str) -> bool {
+    rendered.split('/').any(|part| {
+        let sanitized = sanitize_component(part, separator);
+        !sanitized.is_empty() && !sanitized.starts_with('-')
+    })
 }
 
-/// Find an available branch name, adding _2, _3, etc. if needed.
-fn pick_available_name(candidate: &str, existing: &HashSet<String>) -> Result<String> {
+/// Find an available branch name, adding a collision suffix if needed.
+fn pick_available_name(
+    candidate: &str,
+    existing: &HashSet<String>,
+    suffix_style: SuffixStyle,
+) -> Result<String> {
     if !existing.contains(candidate) {
         return Ok(candidate.to_string());
     }
 
-    // Try with numeric suffix
+    // Try with a numbered suffix
     for i in 2..=100 {
-        let with_suffix = format!("{}_{}", candidate, i);
+        let with_suffix = suffix_style.format(candidate, i);
         if !existing.contains(&with_suffix) {
             return Ok(with_suffix);
         }
@@ -317,81 +400,83 @@ fn pick_available_name(candidate: &str, existing: &HashSet<String>) -> Result<St
     anyhow::bail!("Could not find available branch name after 100 attempts");
 }
 
-/// Create and switch to a new branch.
-fn create_branch(name: &str) -> Result<()> {
-    // Try git switch first (modern git)
-    let switch = Command::new("git")
-        .args(["switch", "-c", name])
-        .output()
-        .context("Failed to run git switch")?;
-
-    if switch.status.success() {
-        return Ok(());
-    }
-
-    // Fall back to git checkout -b (older git)
-    let checkout = Command::new("git")
-        .args(["checkout", "-b", name])
-        .output()
-        .context("Failed to run git checkout")?;
-
-    if !checkout.status.success() {
-        let stderr = String::from_utf8_lossy(&checkout.stderr);
-        anyhow::bail!("Failed to create branch: {}", stderr.trim());
-    }
-
-    Ok(())
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_sanitize_simple() {
-        assert_eq!(sanitize("ABC-123"), "ABC-123");
-        assert_eq!(sanitize("feature"), "feature");
+        assert_eq!(sanitize("ABC-123", "-"), "ABC-123");
+        assert_eq!(sanitize("feature", "-"), "feature");
     }
 
     #[test]
     fn test_sanitize_spaces() {
-        assert_eq!(sanitize("fix login bug"), "fix-login-bug");
-        assert_eq!(sanitize("multiple   spaces"), "multiple-spaces");
+        assert_eq!(sanitize("fix login bug", "-"), "fix-login-bug");
+        assert_eq!(sanitize("multiple   spaces", "-"), "multiple-spaces");
     }
 
     #[test]
     fn test_sanitize_slashes() {
-        assert_eq!(sanitize("feature/sub"), "feature-sub");
+        assert_eq!(sanitize("feature/sub", "-"), "feature-sub");
     }
 
     #[test]
     fn test_sanitize_special_chars() {
-        assert_eq!(sanitize("fix@#$%bug"), "fix-bug");
-        assert_eq!(sanitize("a!b@c#d"), "a-b-c-d");
+        assert_eq!(sanitize("fix@#$%bug", "-"), "fix-bug");
+        assert_eq!(sanitize("a!b@c#d", "-"), "a-b-c-d");
     }
 
     #[test]
     fn test_sanitize_allowed_chars() {
-        assert_eq!(sanitize("v1.2.3"), "v1.2.3");
-        assert_eq!(sanitize("feat_name"), "feat_name");
-        assert_eq!(sanitize("test+plus"), "test+plus");
+        assert_eq!(sanitize("v1.2.3", "-"), "v1.2.3");
+        assert_eq!(sanitize("feat_name", "-"), "feat_name");
+        assert_eq!(sanitize("test+plus", "-"), "test+plus");
     }
 
     #[test]
     fn test_sanitize_dot_edges() {
-        assert_eq!(sanitize(".leading"), "leading");
-        assert_eq!(sanitize("trailing."), "trailing");
-        assert_eq!(sanitize("double..dot"), "double-dot");
+        assert_eq!(sanitize(".leading", "-"), "leading");
+        assert_eq!(sanitize("trailing.", "-"), "trailing");
+        assert_eq!(sanitize("double..dot", "-"), "double-dot");
+    }
+
+    #[test]
+    fn test_sanitize_ref_format_chars() {
+        // ~ ^ : ? * [ \ are all forbidden by git check-ref-format
+        assert_eq!(sanitize("a~b^c:d?e*f[g\\h", "-"), "a-b-c-d-e-f-g-h");
+        // ASCII control characters and DEL
+        assert_eq!(sanitize("a\x01b\x7fc", "-"), "a-b-c");
+    }
+
+    #[test]
+    fn test_sanitize_at_sequences() {
+        // "@{" and a lone "@" are both rejected by git check-ref-format
+        assert_eq!(sanitize_component("feat@{up}", "-"), "feat-up");
+        assert_eq!(sanitize_component("@", "-"), "");
+    }
+
+    #[test]
+    fn test_sanitize_candidate_consecutive_and_trailing_slashes() {
+        assert_eq!(sanitize_candidate("a//b", "-", "fallback"), "a/b");
+        assert_eq!(sanitize_candidate("a/b/", "-", "fallback"), "a/b");
+        assert_eq!(sanitize_candidate("a/.git/b", "-", "fallback"), "a/git/b");
+    }
+
+    #[test]
+    fn test_has_valid_ref_components() {
+        assert!(has_valid_ref_components("prefix/ABC-123", "-"));
+        assert!(!has_valid_ref_components("@/@{}", "-"));
     }
 
     #[test]
     fn test_sanitize_lock_suffix() {
-        assert_eq!(sanitize("build.lock"), "build-lock");
+        assert_eq!(sanitize("build.lock", "-"), "build-lock");
     }
 
     #[test]
     fn test_sanitize_empty_fallback() {
-        let result = sanitize("!@#$%");
+        let result = sanitize("!@#$%", "-");
         // Should be a date in YYMMDD format
         assert_eq!(result.len(), 6);
         assert!(result.chars().all(|c| c.is_ascii_digit()));
@@ -399,32 +484,82 @@ mod tests {
 
     #[test]
     fn test_sanitize_trim_dashes() {
-        assert_eq!(sanitize("-hello-"), "hello");
-        assert_eq!(sanitize("--test--"), "test");
+        assert_eq!(sanitize("-hello-", "-"), "hello");
+        assert_eq!(sanitize("--test--", "-"), "test");
+    }
+
+    #[test]
+    fn test_sanitize_custom_separator() {
+        assert_eq!(sanitize("fix login bug", "_"), "fix_login_bug");
+    }
+
+    #[test]
+    fn test_sanitize_multi_char_separator_collapses_and_trims() {
+        assert_eq!(sanitize("  weird name  ", "--"), "weird--name");
+        assert_eq!(sanitize("--already--dashed--", "--"), "already--dashed");
+    }
+
+    #[test]
+    fn test_sanitize_leading_dash_stripped_regardless_of_separator() {
+        assert_eq!(sanitize("-hello", "_"), "hello");
+        assert_eq!(sanitize_component("-hello-", "_"), "hello-");
+    }
+
+    #[test]
+    fn test_has_valid_ref_components_matches_multi_char_separator_result() {
+        // Previously this reported "valid" (non-empty after sanitizing)
+        // while the actual sanitized name still began with '-' and git
+        // rejected it outright. Now the leading '-' is stripped, so
+        // --check and the real branch creation agree.
+        assert!(has_valid_ref_components("  weird name  ", "--"));
+    }
+
+    #[test]
+    fn test_sanitize_candidate_preserves_slashes() {
+        assert_eq!(
+            sanitize_candidate("alice/fix login", "-", "240101"),
+            "alice/fix-login"
+        );
+    }
+
+    #[test]
+    fn test_sanitize_candidate_empty_fallback() {
+        assert_eq!(sanitize_candidate("!@#/$%^", "-", "240101"), "240101");
+    }
+
+    #[test]
+    fn test_extract_ticket() {
+        assert_eq!(extract_ticket("ABC-123"), Some("ABC-123".to_string()));
+        assert_eq!(extract_ticket("fix login bug"), None);
+        assert_eq!(extract_ticket("240101"), None);
     }
 
     #[test]
     fn test_build_base_name_empty() {
-        let result = build_base_name(&[]);
+        let result = build_base_name(&[], "%y%m%d");
         assert_eq!(result.len(), 6);
         assert!(result.chars().all(|c| c.is_ascii_digit()));
     }
 
     #[test]
     fn test_build_base_name_single() {
-        assert_eq!(build_base_name(&["ABC-123".to_string()]), "ABC-123");
+        assert_eq!(
+            build_base_name(&["ABC-123".to_string()], "%y%m%d"),
+            "ABC-123"
+        );
     }
 
     #[test]
     fn test_build_base_name_multiple() {
         let args = vec!["fix".to_string(), "login".to_string()];
-        assert_eq!(build_base_name(&args), "fix login");
+        assert_eq!(build_base_name(&args, "%y%m%d"), "fix login");
     }
 
     #[test]
     fn test_pick_available_name_no_collision() {
         let existing = HashSet::new();
-        let result = pick_available_name("user/240101", &existing).unwrap();
+        let result =
+            pick_available_name("user/240101", &existing, SuffixStyle::Underscore).unwrap();
         assert_eq!(result, "user/240101");
     }
 
@@ -435,7 +570,17 @@ mod tests {
         existing.insert("user/240101_2".to_string());
         existing.insert("user/240101_3".to_string());
 
-        let result = pick_available_name("user/240101", &existing).unwrap();
+        let result =
+            pick_available_name("user/240101", &existing, SuffixStyle::Underscore).unwrap();
         assert_eq!(result, "user/240101_4");
     }
+
+    #[test]
+    fn test_pick_available_name_dash_suffix_style() {
+        let mut existing = HashSet::new();
+        existing.insert("user/240101".to_string());
+
+        let result = pick_available_name("user/240101", &existing, SuffixStyle::Dash).unwrap();
+        assert_eq!(result, "user/240101-2");
+    }
 }